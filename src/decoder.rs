@@ -0,0 +1,177 @@
+use heapless::Vec;
+
+use crate::{HDLCError, ESCAPED, ESCAPE_MARKER, FRAME_BOUNDARY_MARKER};
+
+/// Internal state of the [`Decoder`] state machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Discarding bytes until the first frame boundary is seen.
+    Idle,
+    /// Accumulating bytes into the buffer.
+    InFrame,
+    /// The previous byte was the escape marker; the next byte is a
+    /// substitution that must be looked up in `ESCAPED`.
+    Escape,
+}
+
+/// Incremental, byte-at-a-time SHDLC decoder.
+///
+/// Feed it bytes as they arrive from a stream (e.g. a UART) with [`push`](Self::push)
+/// instead of buffering a whole frame yourself.
+///
+/// # Example
+/// ```rust
+/// extern crate sensirion_hdlc;
+/// use sensirion_hdlc::Decoder;
+///
+/// let mut decoder = Decoder::<16>::new();
+/// let input = [0x7e, 0x00, 0x01, 0x00, 0xfe, 0x7e];
+/// let mut frame = None;
+/// for byte in input {
+///     frame = decoder.push(byte).unwrap();
+/// }
+/// assert_eq!(frame.unwrap(), [0x00, 0x01, 0x00, 0xfe]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Decoder<const MAX_DECODED_SIZE: usize> {
+    state: State,
+    buffer: Vec<u8, MAX_DECODED_SIZE>,
+}
+
+impl<const MAX_DECODED_SIZE: usize> Decoder<MAX_DECODED_SIZE> {
+    /// Creates a new, empty decoder.
+    pub fn new() -> Self {
+        Self {
+            state: State::Idle,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Feeds a single received byte into the decoder.
+    ///
+    /// Returns `Ok(None)` while the frame is still incomplete, and
+    /// `Ok(Some(frame))` once a closing frame boundary completes one. The
+    /// closing boundary is shared with the next frame, so the decoder stays
+    /// armed and ready to accumulate it.
+    ///
+    /// # Errors
+    ///
+    /// - [`HDLCError::TooMuchData`] if the internal buffer overflows.
+    /// - [`HDLCError::FendCharInData`] or [`HDLCError::MissingTradeChar`] if
+    ///   an escape marker is not followed by a valid substitution byte.
+    pub fn push(&mut self, byte: u8) -> Result<Option<Vec<u8, MAX_DECODED_SIZE>>, HDLCError> {
+        match self.state {
+            State::Idle => {
+                if byte == FRAME_BOUNDARY_MARKER {
+                    self.state = State::InFrame;
+                }
+                Ok(None)
+            }
+            State::Escape => {
+                self.state = State::InFrame;
+                if byte == FRAME_BOUNDARY_MARKER {
+                    return Err(HDLCError::MissingTradeChar);
+                }
+                let (org, _) = ESCAPED
+                    .iter()
+                    .find(|(_, escaped)| *escaped == byte)
+                    .ok_or(HDLCError::FendCharInData)?;
+                self.buffer.push(*org).map_err(|_| HDLCError::TooMuchData)?;
+                Ok(None)
+            }
+            State::InFrame => {
+                if byte == FRAME_BOUNDARY_MARKER {
+                    if self.buffer.is_empty() {
+                        // Two adjacent boundaries: reset rather than emit an
+                        // empty frame.
+                        return Ok(None);
+                    }
+                    let frame = core::mem::replace(&mut self.buffer, Vec::new());
+                    Ok(Some(frame))
+                } else if byte == ESCAPE_MARKER {
+                    self.state = State::Escape;
+                    Ok(None)
+                } else {
+                    self.buffer.push(byte).map_err(|_| HDLCError::TooMuchData)?;
+                    Ok(None)
+                }
+            }
+        }
+    }
+}
+
+impl<const MAX_DECODED_SIZE: usize> Default for Decoder<MAX_DECODED_SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_all<const N: usize>(decoder: &mut Decoder<N>, bytes: &[u8]) -> Vec<Vec<u8, N>, 4> {
+        let mut frames = Vec::new();
+        for &byte in bytes {
+            if let Some(frame) = decoder.push(byte).unwrap() {
+                frames.push(frame).unwrap();
+            }
+        }
+        frames
+    }
+
+    #[test]
+    fn decodes_single_frame() {
+        let mut decoder = Decoder::<16>::new();
+        let input = [0x7e, 0x00, 0x01, 0x00, 0xfe, 0x7e];
+        let frames = push_all(&mut decoder, &input);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0], [0x00, 0x01, 0x00, 0xfe]);
+    }
+
+    #[test]
+    fn decodes_back_to_back_frames_sharing_a_boundary() {
+        let mut decoder = Decoder::<16>::new();
+        let input = [0x7e, 0x01, 0x02, 0x7e, 0x03, 0x04, 0x7e];
+        let frames = push_all(&mut decoder, &input);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0], [0x01, 0x02]);
+        assert_eq!(frames[1], [0x03, 0x04]);
+    }
+
+    #[test]
+    fn ignores_leading_garbage_before_first_boundary() {
+        let mut decoder = Decoder::<16>::new();
+        let input = [0xff, 0xff, 0x7e, 0x01, 0x7e];
+        let frames = push_all(&mut decoder, &input);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0], [0x01]);
+    }
+
+    #[test]
+    fn adjacent_boundaries_reset_instead_of_emitting_empty_frame() {
+        let mut decoder = Decoder::<16>::new();
+        let input = [0x7e, 0x7e, 0x01, 0x7e];
+        let frames = push_all(&mut decoder, &input);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0], [0x01]);
+    }
+
+    #[test]
+    fn unescapes_escaped_bytes() {
+        let mut decoder = Decoder::<16>::new();
+        let input = [0x7e, 0x7d, 0x5e, 0x7e];
+        let frames = push_all(&mut decoder, &input);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0], [0x7e]);
+    }
+
+    #[test]
+    fn overflow_returns_too_much_data() {
+        let mut decoder = Decoder::<2>::new();
+        assert_eq!(decoder.push(0x7e), Ok(None));
+        assert_eq!(decoder.push(0x01), Ok(None));
+        assert_eq!(decoder.push(0x02), Ok(None));
+        assert_eq!(decoder.push(0x03), Err(HDLCError::TooMuchData));
+    }
+}
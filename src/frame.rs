@@ -0,0 +1,239 @@
+use heapless::Vec;
+
+use crate::{decode_into, encode, HDLCError};
+
+/// Maximum number of bytes a decoded SHDLC frame (header, data and checksum)
+/// may contain before it is rejected.
+const MAX_DECODED_FRAME_SIZE: usize = 259;
+
+/// Scratch buffer size used while unescaping a MISO frame. Kept larger than
+/// `MAX_DECODED_FRAME_SIZE` so an oversized frame can actually be detected
+/// and rejected with `HDLCError::TooMuchDecodedData`, instead of `decode_into`
+/// failing on it first with `HDLCError::TooMuchData`.
+const RAW_DECODE_BUFFER_SIZE: usize = MAX_DECODED_FRAME_SIZE + 16;
+
+/// Computes the SHDLC checksum: the inverted least-significant byte of the
+/// sum of all passed bytes.
+fn checksum(bytes: &[u8]) -> u8 {
+    !bytes.iter().fold(0u8, |acc, &byte| acc.wrapping_add(byte))
+}
+
+/// A host-to-device (MOSI) SHDLC frame: `[Addr, Cmd, Length, Data…, Chk]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MosiFrame<'a> {
+    /// Device address the frame is addressed to.
+    pub address: u8,
+    /// Command byte.
+    pub command: u8,
+    /// Command data.
+    pub data: &'a [u8],
+}
+
+impl<'a> MosiFrame<'a> {
+    /// Builds a new MOSI frame from its address, command and data.
+    pub fn new(address: u8, command: u8, data: &'a [u8]) -> Self {
+        Self {
+            address,
+            command,
+            data,
+        }
+    }
+
+    /// Builds the `[Addr, Cmd, Length, Data…, Chk]` header, appends the
+    /// computed checksum, then byte-stuffs and frames the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HDLCError::TooMuchData`] if `data` is longer than 255 bytes
+    /// or if `MAX_ENCODED_SIZE` is too small to hold the escaped frame.
+    pub fn encode<const MAX_ENCODED_SIZE: usize>(
+        &self,
+    ) -> Result<Vec<u8, MAX_ENCODED_SIZE>, HDLCError> {
+        if self.data.len() > u8::MAX as usize {
+            return Err(HDLCError::TooMuchData);
+        }
+
+        let mut header: Vec<u8, MAX_DECODED_FRAME_SIZE> = Vec::new();
+        header
+            .push(self.address)
+            .map_err(|_| HDLCError::TooMuchData)?;
+        header
+            .push(self.command)
+            .map_err(|_| HDLCError::TooMuchData)?;
+        header
+            .push(self.data.len() as u8)
+            .map_err(|_| HDLCError::TooMuchData)?;
+        header
+            .extend_from_slice(self.data)
+            .map_err(|_| HDLCError::TooMuchData)?;
+        let chk = checksum(&header);
+        header.push(chk).map_err(|_| HDLCError::TooMuchData)?;
+
+        encode(&header)
+    }
+}
+
+/// A device-to-host (MISO) SHDLC frame: `[Addr, Cmd, State, Length, Data…, Chk]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MisoFrame<const MAX_DATA_SIZE: usize> {
+    /// Device address the frame was sent from.
+    pub address: u8,
+    /// Command byte this is the response to.
+    pub command: u8,
+    /// Device execution state.
+    pub state: u8,
+    /// Response data.
+    pub data: Vec<u8, MAX_DATA_SIZE>,
+}
+
+impl<const MAX_DATA_SIZE: usize> MisoFrame<MAX_DATA_SIZE> {
+    /// Unescapes `input` and parses it as a MISO frame, verifying the
+    /// declared length and checksum.
+    ///
+    /// # Errors
+    ///
+    /// - [`HDLCError::TooFewData`] if the unescaped frame is shorter than
+    ///   the `[Addr, Cmd, State, Length, Chk]` header, or if `Length` is
+    ///   greater than the number of data bytes actually received.
+    /// - [`HDLCError::TooMuchDecodedData`] if more than 259 bytes result
+    ///   from decoding, or if `Length` is less than the number of data
+    ///   bytes actually received.
+    /// - [`HDLCError::InvalidChecksum`] if the trailing checksum byte does
+    ///   not match the recomputed one.
+    ///
+    /// See [`crate::decode`] for the byte-stuffing errors that can also occur.
+    pub fn decode(input: &[u8]) -> Result<Self, HDLCError> {
+        let mut buf = [0u8; RAW_DECODE_BUFFER_SIZE];
+        // `buf` is sized strictly larger than `MAX_DECODED_FRAME_SIZE`, so a
+        // `TooMuchData` here can only mean the decoded frame is oversized,
+        // regardless of how far past the buffer's capacity it actually goes.
+        let len = decode_into(input, &mut buf).map_err(|err| match err {
+            HDLCError::TooMuchData => HDLCError::TooMuchDecodedData,
+            err => err,
+        })?;
+        let raw = &buf[..len];
+
+        if raw.len() > MAX_DECODED_FRAME_SIZE {
+            return Err(HDLCError::TooMuchDecodedData);
+        }
+        if raw.len() < 5 {
+            return Err(HDLCError::TooFewData);
+        }
+
+        let address = raw[0];
+        let command = raw[1];
+        let state = raw[2];
+        let length = raw[3] as usize;
+        let data = &raw[4..raw.len() - 1];
+        if data.len() < length {
+            return Err(HDLCError::TooFewData);
+        }
+        if data.len() > length {
+            return Err(HDLCError::TooMuchDecodedData);
+        }
+
+        let expected_chk = raw[raw.len() - 1];
+        let chk = checksum(&raw[..raw.len() - 1]);
+        if chk != expected_chk {
+            return Err(HDLCError::InvalidChecksum);
+        }
+
+        let mut owned_data = Vec::new();
+        owned_data
+            .extend_from_slice(data)
+            .map_err(|_| HDLCError::TooMuchData)?;
+
+        Ok(Self {
+            address,
+            command,
+            state,
+            data: owned_data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mosi_frame_roundtrip_checksum() {
+        let frame = MosiFrame::new(0x00, 0x00, &[0x01, 0x03]);
+        let encoded: Vec<u8, 20> = frame.encode().unwrap();
+        // 0x00 + 0x00 + 0x02 (len) + 0x01 + 0x03 = 0x06, chk = !0x06 = 0xf9
+        let expected = [0x7e, 0x00, 0x00, 0x02, 0x01, 0x03, 0xf9, 0x7e];
+        assert_eq!(encoded[0..encoded.len()], expected);
+    }
+
+    #[test]
+    fn miso_frame_decode() {
+        // address 0x00, command 0x00, state 0x00, length 0x02, data [0x01, 0x03]
+        let header = [0x00, 0x00, 0x00, 0x02, 0x01, 0x03];
+        let chk = checksum(&header);
+        let input = [0x7e, 0x00, 0x00, 0x00, 0x02, 0x01, 0x03, chk, 0x7e];
+        let frame: MisoFrame<20> = MisoFrame::decode(&input).unwrap();
+        assert_eq!(frame.address, 0x00);
+        assert_eq!(frame.command, 0x00);
+        assert_eq!(frame.state, 0x00);
+        assert_eq!(frame.data, [0x01, 0x03]);
+    }
+
+    #[test]
+    fn miso_frame_rejects_bad_checksum() {
+        let input = [0x7e, 0x00, 0x00, 0x00, 0x02, 0x01, 0x03, 0x00, 0x7e];
+        let result: Result<MisoFrame<20>, HDLCError> = MisoFrame::decode(&input);
+        assert_eq!(result, Err(HDLCError::InvalidChecksum));
+    }
+
+    #[test]
+    fn miso_frame_rejects_oversized_decoded_data() {
+        let length = 255u8;
+        let mut header = [0u8; 4 + 255];
+        header[3] = length;
+        let chk = checksum(&header);
+
+        let mut raw = [0u8; 4 + 255 + 1];
+        raw[..header.len()].copy_from_slice(&header);
+        raw[header.len()] = chk;
+
+        let mut encoded = [0u8; 4 + 255 + 1 + 2];
+        let written = crate::encode_into(&raw, &mut encoded).unwrap();
+
+        let result: Result<MisoFrame<300>, HDLCError> = MisoFrame::decode(&encoded[..written]);
+        assert_eq!(result, Err(HDLCError::TooMuchDecodedData));
+    }
+
+    #[test]
+    fn miso_frame_rejects_decoded_data_past_the_scratch_buffer() {
+        // Decodes to more bytes than `RAW_DECODE_BUFFER_SIZE` itself can
+        // hold, not just more than `MAX_DECODED_FRAME_SIZE`.
+        let raw = [0u8; 310];
+        let mut encoded = [0u8; 310 + 2];
+        let written = crate::encode_into(&raw, &mut encoded).unwrap();
+
+        let result: Result<MisoFrame<400>, HDLCError> = MisoFrame::decode(&encoded[..written]);
+        assert_eq!(result, Err(HDLCError::TooMuchDecodedData));
+    }
+
+    #[test]
+    fn miso_frame_rejects_length_shorter_than_data() {
+        // Declared length (1) is less than the two data bytes actually
+        // present.
+        let header = [0x00, 0x00, 0x00, 0x01, 0x01, 0x03];
+        let chk = checksum(&header);
+        let input = [0x7e, 0x00, 0x00, 0x00, 0x01, 0x01, 0x03, chk, 0x7e];
+        let result: Result<MisoFrame<20>, HDLCError> = MisoFrame::decode(&input);
+        assert_eq!(result, Err(HDLCError::TooMuchDecodedData));
+    }
+
+    #[test]
+    fn miso_frame_rejects_length_longer_than_data() {
+        // Declared length (3) is more than the two data bytes actually
+        // present.
+        let header = [0x00, 0x00, 0x00, 0x03, 0x01, 0x03];
+        let chk = checksum(&header);
+        let input = [0x7e, 0x00, 0x00, 0x00, 0x03, 0x01, 0x03, chk, 0x7e];
+        let result: Result<MisoFrame<20>, HDLCError> = MisoFrame::decode(&input);
+        assert_eq!(result, Err(HDLCError::TooFewData));
+    }
+}
@@ -2,8 +2,14 @@
 
 use heapless::Vec;
 
+mod decoder;
 mod error;
+mod frame;
+mod special_chars;
+pub use decoder::Decoder;
 pub use error::HDLCError;
+pub use frame::{MisoFrame, MosiFrame};
+pub use special_chars::SpecialChars;
 
 const ESCAPE_MARKER: u8 = 0x7d;
 const FRAME_BOUNDARY_MARKER: u8 = 0x7e;
@@ -26,28 +32,102 @@ const ESCAPED: [(u8, u8); 4] = [(0x7d, 0x5d), (0x7e, 0x5e), (0x11, 0x31), (0x13,
 pub fn encode<const MAX_ENCODED_SIZE: usize>(
     data: &[u8],
 ) -> Result<Vec<u8, MAX_ENCODED_SIZE>, HDLCError> {
-    // -2 for the fend start and stop bytes
-    if data.len() > MAX_ENCODED_SIZE / 2 - 2 {
-        return Err(HDLCError::TooMuchData);
-    }
+    let mut buf = [0u8; MAX_ENCODED_SIZE];
+    let written = encode_into(data, &mut buf)?;
 
     let mut output = Vec::new();
-    output.push(FRAME_BOUNDARY_MARKER)?;
+    output
+        .extend_from_slice(&buf[..written])
+        .map_err(|_| HDLCError::TooMuchData)?;
+    Ok(output)
+}
+
+/// Byte-stuffs `data` directly into `out`, returning the number of bytes
+/// written, instead of allocating a `heapless::Vec`.
+///
+/// This lets embedded users drive a single preallocated DMA/UART buffer
+/// rather than fixing the capacity as a const generic at every call site.
+///
+/// # Errors
+///
+/// Returns [`HDLCError::TooMuchData`] if `out` is too small to hold the
+/// escaped frame.
+pub fn encode_into(data: &[u8], out: &mut [u8]) -> Result<usize, HDLCError> {
+    let mut written = 0;
+    let push = |out: &mut [u8], written: &mut usize, byte: u8| -> Result<(), HDLCError> {
+        *out.get_mut(*written).ok_or(HDLCError::TooMuchData)? = byte;
+        *written += 1;
+        Ok(())
+    };
+
+    push(out, &mut written, FRAME_BOUNDARY_MARKER)?;
     for &byte in data {
-        for (org, replacement) in ESCAPED {
-            if byte == org {
-                output.push(ESCAPE_MARKER)?;
-                output.push(replacement)?;
-                continue;
-            }
+        if let Some(&(_, replacement)) = ESCAPED.iter().find(|(org, _)| *org == byte) {
+            push(out, &mut written, ESCAPE_MARKER)?;
+            push(out, &mut written, replacement)?;
+        } else {
+            push(out, &mut written, byte)?;
         }
-        output.push(byte)?;
     }
-    output.push(FRAME_BOUNDARY_MARKER)?;
+    push(out, &mut written, FRAME_BOUNDARY_MARKER)?;
 
+    Ok(written)
+}
+
+/// Like [`encode`], but byte-stuffs using a caller-provided [`SpecialChars`]
+/// instead of the hard-coded Sensirion markers.
+///
+/// # Errors
+///
+/// If the passed `MAX_ENCODED_SIZE` is too small this returns
+/// `HDLCError::TooMuchData`
+pub fn encode_with<const MAX_ENCODED_SIZE: usize>(
+    data: &[u8],
+    chars: &SpecialChars,
+) -> Result<Vec<u8, MAX_ENCODED_SIZE>, HDLCError> {
+    let mut buf = [0u8; MAX_ENCODED_SIZE];
+    let written = encode_into_with(data, &mut buf, chars)?;
+
+    let mut output = Vec::new();
+    output
+        .extend_from_slice(&buf[..written])
+        .map_err(|_| HDLCError::TooMuchData)?;
     Ok(output)
 }
 
+/// Like [`encode_into`], but byte-stuffs using a caller-provided
+/// [`SpecialChars`] instead of the hard-coded Sensirion markers.
+///
+/// # Errors
+///
+/// Returns [`HDLCError::TooMuchData`] if `out` is too small to hold the
+/// escaped frame.
+pub fn encode_into_with(
+    data: &[u8],
+    out: &mut [u8],
+    chars: &SpecialChars,
+) -> Result<usize, HDLCError> {
+    let mut written = 0;
+    let push = |out: &mut [u8], written: &mut usize, byte: u8| -> Result<(), HDLCError> {
+        *out.get_mut(*written).ok_or(HDLCError::TooMuchData)? = byte;
+        *written += 1;
+        Ok(())
+    };
+
+    push(out, &mut written, chars.frame_boundary)?;
+    for &byte in data {
+        if let Some(&(_, replacement)) = chars.escaped.iter().find(|(org, _)| *org == byte) {
+            push(out, &mut written, chars.escape)?;
+            push(out, &mut written, replacement)?;
+        } else {
+            push(out, &mut written, byte)?;
+        }
+    }
+    push(out, &mut written, chars.frame_boundary)?;
+
+    Ok(written)
+}
+
 /// Produces unescaped (decoded) message without `FEND` characters.
 ///
 /// # Errors
@@ -72,6 +152,34 @@ pub fn encode<const MAX_ENCODED_SIZE: usize>(
 pub fn decode<const MAX_DECODED_SIZE: usize>(
     input: &[u8],
 ) -> Result<Vec<u8, MAX_DECODED_SIZE>, HDLCError> {
+    let mut buf = [0u8; MAX_DECODED_SIZE];
+    let written = decode_into(input, &mut buf)?;
+
+    let mut output = Vec::new();
+    output
+        .extend_from_slice(&buf[..written])
+        .map_err(|_| HDLCError::TooMuchData)?;
+    Ok(output)
+}
+
+/// Unescapes `input` directly into `out`, returning the number of bytes
+/// written, instead of allocating a `heapless::Vec`.
+///
+/// This lets embedded users drive a single preallocated DMA/UART buffer
+/// rather than fixing the capacity as a const generic at every call site.
+///
+/// # Errors
+/// The following errors can occur while decoding:
+///
+/// - [`HDLCError::TooMuchData`]
+/// - [`HDLCError::FendCharInData`]
+/// - [`HDLCError::MissingTradeChar`]
+/// - [`HDLCError::MissingFirstFend`]
+/// - [`HDLCError::MissingFinalFend`]
+/// - [`HDLCError::TooFewData`]
+///
+/// See the error type documentation for more.
+pub fn decode_into(input: &[u8], out: &mut [u8]) -> Result<usize, HDLCError> {
     if input.len() < 4 {
         return Err(HDLCError::TooFewData);
     }
@@ -85,7 +193,7 @@ pub fn decode<const MAX_DECODED_SIZE: usize>(
         return Err(HDLCError::MissingFinalFend);
     }
 
-    let mut output = Vec::new();
+    let mut written = 0;
 
     // Iterator over the input that allows peeking
     let mut input = input[1..input.len() - 1].iter();
@@ -93,7 +201,7 @@ pub fn decode<const MAX_DECODED_SIZE: usize>(
     // Loop over every byte of the message
     while let Some(&byte) = input.next() {
         // Handle a FESC
-        if byte == ESCAPE_MARKER {
+        let decoded = if byte == ESCAPE_MARKER {
             let Some(&escaped_byte) = input.next() else {
                 return Err(HDLCError::MissingTradeChar);
             };
@@ -101,15 +209,125 @@ pub fn decode<const MAX_DECODED_SIZE: usize>(
                 .iter()
                 .find(|(_, escaped)| *escaped == escaped_byte)
                 .ok_or(HDLCError::FendCharInData)?;
-            output.push(*org)?;
+            *org
         } else {
-            output.push(byte)?;
-        }
+            byte
+        };
+        *out.get_mut(written).ok_or(HDLCError::TooMuchData)? = decoded;
+        written += 1;
     }
 
+    Ok(written)
+}
+
+/// Computes the exact number of bytes `encode`/`encode_into` will write for
+/// `data`, including the two frame boundary bytes.
+///
+/// Useful for sizing a const-generic `N` or an `encode_into` output slice
+/// exactly, instead of guessing a `MAX_ENCODED_SIZE`.
+pub fn encoded_len(data: &[u8]) -> usize {
+    let escaped_count = data
+        .iter()
+        .filter(|byte| ESCAPED.iter().any(|(org, _)| org == *byte))
+        .count();
+    2 + data.len() + escaped_count
+}
+
+/// Computes the largest number of bytes a `decode`/`decode_into` of `input`
+/// could produce.
+pub fn max_decoded_len(input: &[u8]) -> usize {
+    input.len().saturating_sub(2)
+}
+
+/// Like [`decode`], but unescapes using a caller-provided [`SpecialChars`]
+/// instead of the hard-coded Sensirion markers.
+///
+/// # Errors
+/// The following errors can occur while decoding:
+///
+/// - [`HDLCError::TooMuchData`]
+/// - [`HDLCError::FendCharInData`]
+/// - [`HDLCError::MissingTradeChar`]
+/// - [`HDLCError::MissingFirstFend`]
+/// - [`HDLCError::MissingFinalFend`]
+/// - [`HDLCError::TooFewData`]
+/// - [`HDLCError::TooMuchData`]
+///
+/// See the error type documentation for more.
+pub fn decode_with<const MAX_DECODED_SIZE: usize>(
+    input: &[u8],
+    chars: &SpecialChars,
+) -> Result<Vec<u8, MAX_DECODED_SIZE>, HDLCError> {
+    let mut buf = [0u8; MAX_DECODED_SIZE];
+    let written = decode_into_with(input, &mut buf, chars)?;
+
+    let mut output = Vec::new();
+    output
+        .extend_from_slice(&buf[..written])
+        .map_err(|_| HDLCError::TooMuchData)?;
     Ok(output)
 }
 
+/// Like [`decode_into`], but unescapes using a caller-provided
+/// [`SpecialChars`] instead of the hard-coded Sensirion markers.
+///
+/// # Errors
+/// The following errors can occur while decoding:
+///
+/// - [`HDLCError::TooMuchData`]
+/// - [`HDLCError::FendCharInData`]
+/// - [`HDLCError::MissingTradeChar`]
+/// - [`HDLCError::MissingFirstFend`]
+/// - [`HDLCError::MissingFinalFend`]
+/// - [`HDLCError::TooFewData`]
+///
+/// See the error type documentation for more.
+pub fn decode_into_with(
+    input: &[u8],
+    out: &mut [u8],
+    chars: &SpecialChars,
+) -> Result<usize, HDLCError> {
+    if input.len() < 4 {
+        return Err(HDLCError::TooFewData);
+    }
+
+    // Verify input begins with a FEND
+    if input[0] != chars.frame_boundary {
+        return Err(HDLCError::MissingFirstFend);
+    }
+    // Verify input ends with a FEND
+    if input[input.len() - 1] != chars.frame_boundary {
+        return Err(HDLCError::MissingFinalFend);
+    }
+
+    let mut written = 0;
+
+    // Iterator over the input that allows peeking
+    let mut input = input[1..input.len() - 1].iter();
+
+    // Loop over every byte of the message
+    while let Some(&byte) = input.next() {
+        // Handle a FESC
+        let decoded = if byte == chars.escape {
+            let Some(&escaped_byte) = input.next() else {
+                return Err(HDLCError::MissingTradeChar);
+            };
+            let (org, _) = chars
+                .escaped
+                .iter()
+                .find(|(_, escaped)| *escaped == escaped_byte)
+                .ok_or(HDLCError::FendCharInData)?;
+            *org
+        } else {
+            byte
+        };
+        *out.get_mut(written).ok_or(HDLCError::TooMuchData)? = decoded;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +355,122 @@ mod tests {
         let encoded: Vec<u8, 10> = decode(&mosi_data).unwrap();
         assert_eq!(encoded[0..encoded.len()], expected);
     }
+
+    #[test]
+    fn encode_with_default_matches_encode() {
+        let mosi_data = [0x00, 0x01, 0x00, 0xfe];
+        let chars = SpecialChars::default();
+        let expected: Vec<u8, 15> = encode(&mosi_data).unwrap();
+        let encoded: Vec<u8, 15> = encode_with(&mosi_data, &chars).unwrap();
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    fn encode_with_escapes_each_special_byte_exactly_once() {
+        let chars = SpecialChars::default();
+        for &(org, replacement) in &ESCAPED {
+            let data = [org];
+            let encoded: Vec<u8, 4> = encode_with(&data, &chars).unwrap();
+            assert_eq!(
+                encoded[0..encoded.len()],
+                [
+                    FRAME_BOUNDARY_MARKER,
+                    ESCAPE_MARKER,
+                    replacement,
+                    FRAME_BOUNDARY_MARKER
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn encode_with_capacity_matches_encode() {
+        let data = [0x01u8; 20];
+        let encoded: Vec<u8, 22> = encode(&data).unwrap();
+        let encoded_with: Vec<u8, 22> = encode_with(&data, &SpecialChars::default()).unwrap();
+        assert_eq!(encoded_with, encoded);
+    }
+
+    #[test]
+    fn encode_into_writes_into_caller_buffer() {
+        let mosi_data = [0x00, 0x01, 0x00, 0xfe];
+        let mut buf = [0u8; 15];
+        let written = encode_into(&mosi_data, &mut buf).unwrap();
+        assert_eq!(buf[..written], [0x7e, 0x00, 0x01, 0x00, 0xfe, 0x7e]);
+    }
+
+    #[test]
+    fn encode_into_escapes_each_special_byte_exactly_once() {
+        for &(org, replacement) in &ESCAPED {
+            let data = [org];
+            let mut buf = [0u8; 4];
+            let written = encode_into(&data, &mut buf).unwrap();
+            assert_eq!(
+                buf[..written],
+                [
+                    FRAME_BOUNDARY_MARKER,
+                    ESCAPE_MARKER,
+                    replacement,
+                    FRAME_BOUNDARY_MARKER
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn encode_into_too_small_buffer_errors() {
+        let mosi_data = [0x00, 0x01, 0x00, 0xfe];
+        let mut buf = [0u8; 4];
+        assert_eq!(
+            encode_into(&mosi_data, &mut buf),
+            Err(HDLCError::TooMuchData)
+        );
+    }
+
+    #[test]
+    fn decode_into_writes_into_caller_buffer() {
+        let mosi_data = [0x7e, 0x00, 0x01, 0x00, 0xfe, 0x7e];
+        let mut buf = [0u8; 10];
+        let written = decode_into(&mosi_data, &mut buf).unwrap();
+        assert_eq!(buf[..written], [0x00, 0x01, 0x00, 0xfe]);
+    }
+
+    #[test]
+    fn encoded_len_matches_encode_into_without_escapes() {
+        let mosi_data = [0x00, 0x01, 0x00, 0xfe];
+        let mut buf = [0u8; 15];
+        let written = encode_into(&mosi_data, &mut buf).unwrap();
+        assert_eq!(encoded_len(&mosi_data), written);
+    }
+
+    #[test]
+    fn encoded_len_counts_escaped_bytes() {
+        let data = [0x7e, 0x7d, 0x01];
+        assert_eq!(encoded_len(&data), 2 + data.len() + 2);
+
+        let mut buf = [0u8; 15];
+        let written = encode_into(&data, &mut buf).unwrap();
+        assert_eq!(encoded_len(&data), written);
+    }
+
+    #[test]
+    fn max_decoded_len_bounds_decode_into() {
+        let mosi_data = [0x7e, 0x00, 0x01, 0x00, 0xfe, 0x7e];
+        let mut buf = [0u8; 10];
+        let written = decode_into(&mosi_data, &mut buf).unwrap();
+        assert!(written <= max_decoded_len(&mosi_data));
+    }
+
+    #[test]
+    fn decode_with_custom_markers() {
+        let chars = SpecialChars::new(
+            0xc0,
+            0xdb,
+            [(0xc0, 0xdc), (0xdb, 0xdd), (0x11, 0x31), (0x13, 0x33)],
+        )
+        .unwrap();
+        let input = [0xc0, 0x01, 0x02, 0xc0];
+        let decoded: Vec<u8, 10> = decode_with(&input, &chars).unwrap();
+        assert_eq!(decoded[0..decoded.len()], [0x01, 0x02]);
+    }
 }
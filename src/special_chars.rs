@@ -0,0 +1,115 @@
+use heapless::Vec;
+
+use crate::{HDLCError, ESCAPED, ESCAPE_MARKER, FRAME_BOUNDARY_MARKER};
+
+/// A configurable set of special bytes used for framing and byte-stuffing.
+///
+/// [`encode_with`](crate::encode_with)/[`decode_with`](crate::decode_with) use
+/// this instead of the hard-coded Sensirion markers, so the same engine can
+/// serve other vendors' KISS/HDLC-style framings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecialChars {
+    pub(crate) frame_boundary: u8,
+    pub(crate) escape: u8,
+    pub(crate) escaped: [(u8, u8); 4],
+}
+
+impl SpecialChars {
+    /// Builds a new set of special characters.
+    ///
+    /// `escaped` holds the (original, replacement) trade pairs substituted
+    /// for `frame_boundary` and `escape` when they appear in the data. `org`
+    /// is expected to equal `frame_boundary` or `escape` for at least two of
+    /// the pairs, since those are exactly the bytes that need escaping.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HDLCError::DuplicateSpecialChar`] if:
+    /// - `frame_boundary`, `escape`, or any trade pair's `replacement` byte
+    ///   is repeated, since those are the literal bytes that appear in the
+    ///   byte-stuffed stream and must be unambiguous.
+    /// - the same `org` byte is escaped by more than one trade pair.
+    pub fn new(frame_boundary: u8, escape: u8, escaped: [(u8, u8); 4]) -> Result<Self, HDLCError> {
+        let mut literals: Vec<u8, 6> = Vec::new();
+        literals
+            .push(frame_boundary)
+            .map_err(|_| HDLCError::DuplicateSpecialChar)?;
+        literals
+            .push(escape)
+            .map_err(|_| HDLCError::DuplicateSpecialChar)?;
+        for (_, replacement) in escaped {
+            literals
+                .push(replacement)
+                .map_err(|_| HDLCError::DuplicateSpecialChar)?;
+        }
+
+        for i in 0..literals.len() {
+            for j in (i + 1)..literals.len() {
+                if literals[i] == literals[j] {
+                    return Err(HDLCError::DuplicateSpecialChar);
+                }
+            }
+        }
+
+        for i in 0..escaped.len() {
+            for j in (i + 1)..escaped.len() {
+                if escaped[i].0 == escaped[j].0 {
+                    return Err(HDLCError::DuplicateSpecialChar);
+                }
+            }
+        }
+
+        Ok(Self {
+            frame_boundary,
+            escape,
+            escaped,
+        })
+    }
+}
+
+impl Default for SpecialChars {
+    /// Reproduces the hard-coded Sensirion SHDLC byte choices used by
+    /// [`encode`](crate::encode)/[`decode`](crate::decode).
+    fn default() -> Self {
+        Self {
+            frame_boundary: FRAME_BOUNDARY_MARKER,
+            escape: ESCAPE_MARKER,
+            escaped: ESCAPED,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_matches_sensirion_markers() {
+        let chars = SpecialChars::default();
+        assert_eq!(chars.frame_boundary, FRAME_BOUNDARY_MARKER);
+        assert_eq!(chars.escape, ESCAPE_MARKER);
+        assert_eq!(chars.escaped, ESCAPED);
+    }
+
+    #[test]
+    fn new_accepts_sensirion_markers() {
+        let chars = SpecialChars::new(FRAME_BOUNDARY_MARKER, ESCAPE_MARKER, ESCAPED).unwrap();
+        assert_eq!(chars, SpecialChars::default());
+    }
+
+    #[test]
+    fn rejects_duplicate_special_chars() {
+        let result = SpecialChars::new(0x7e, 0x7e, ESCAPED);
+        assert_eq!(result, Err(HDLCError::DuplicateSpecialChar));
+    }
+
+    #[test]
+    fn rejects_duplicate_trade_pair_byte() {
+        let result = SpecialChars::new(
+            0x7e,
+            0x7d,
+            [(0x7d, 0x5d), (0x11, 0x5d), (0x13, 0x33), (0x01, 0x02)],
+        );
+        assert_eq!(result, Err(HDLCError::DuplicateSpecialChar));
+    }
+}